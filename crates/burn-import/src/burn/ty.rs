@@ -5,28 +5,352 @@ use quote::quote;
 
 use crate::burn::ToTokens;
 
+/// A single tensor axis: either a statically known extent, or a named symbolic
+/// axis (e.g. the `batch` or `sequence_len` dim exported by ONNX models with
+/// dynamic axes).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Dim {
+    Concrete(usize),
+    Symbolic(Ident),
+}
+
+impl Dim {
+    /// Returns the static extent of this dim, if it has one.
+    pub fn as_concrete(&self) -> Option<usize> {
+        match self {
+            Dim::Concrete(size) => Some(*size),
+            Dim::Symbolic(_) => None,
+        }
+    }
+
+    pub fn is_concrete(&self) -> bool {
+        self.as_concrete().is_some()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TensorType {
     pub name: Ident,
     pub dim: usize,
     pub kind: TensorKind,
-    pub shape: Option<Vec<usize>>,
+    pub shape: Option<Vec<Dim>>,
+    /// The fully resolved shape, cached at construction time. `Some` only when
+    /// every axis in `shape` is `Dim::Concrete`.
+    concrete: Option<Vec<usize>>,
 }
 
+/// The family a numeric type belongs to, independent of its width.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum TensorKind {
-    Int,
+pub enum TypeKind {
+    Bool,
+    UInt,
+    SInt,
     Float,
+}
+
+/// The bit width of a numeric type, independent of its family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeBits {
+    Bits8,
+    Bits16,
+    Bits32,
+    Bits64,
+}
+
+impl TypeBits {
+    pub fn bits(&self) -> u32 {
+        match self {
+            TypeBits::Bits8 => 8,
+            TypeBits::Bits16 => 16,
+            TypeBits::Bits32 => 32,
+            TypeBits::Bits64 => 64,
+        }
+    }
+}
+
+/// Whether `kind` at `bits` is a representable numeric type: a float needs at
+/// least 16 bits to carry a useful mantissa, and bool is always a single byte.
+pub fn is_valid_numeric_type(kind: TypeKind, bits: TypeBits) -> bool {
+    match kind {
+        TypeKind::Bool => bits == TypeBits::Bits8,
+        TypeKind::Float => bits != TypeBits::Bits8,
+        TypeKind::UInt | TypeKind::SInt => true,
+    }
+}
+
+/// The scale factor of a [`QuantScheme`]: either a constant folded in at import
+/// time, or threaded through from a named runtime value (e.g. a per-channel
+/// scale vector computed elsewhere in the graph).
+#[derive(Debug, Clone, PartialEq)]
+pub enum QuantScale {
+    Concrete(f64),
+    Symbolic(Ident),
+}
+
+/// The affine quantization parameters ONNX attaches to `QuantizeLinear` /
+/// `DequantizeLinear` pairs: `real_value = (stored_value - zero_point) * scale`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantScheme {
+    pub scale: QuantScale,
+    pub zero_point: i64,
+    pub storage: ScalarKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TensorKind {
     Bool,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    Float16,
+    BFloat16,
+    Float32,
+    Float64,
+    /// A tensor stored as `storage`'s integer type, reconstructed to a real
+    /// value via `scheme`'s scale/zero-point.
+    Quantized(QuantScheme),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+impl TensorKind {
+    pub fn type_kind(&self) -> TypeKind {
+        match self {
+            TensorKind::Bool => TypeKind::Bool,
+            TensorKind::UInt8 | TensorKind::UInt16 | TensorKind::UInt32 | TensorKind::UInt64 => {
+                TypeKind::UInt
+            }
+            TensorKind::Int8 | TensorKind::Int16 | TensorKind::Int32 | TensorKind::Int64 => {
+                TypeKind::SInt
+            }
+            TensorKind::Float16
+            | TensorKind::BFloat16
+            | TensorKind::Float32
+            | TensorKind::Float64 => TypeKind::Float,
+            TensorKind::Quantized(scheme) => scheme.storage.type_kind(),
+        }
+    }
+
+    pub fn type_bits(&self) -> TypeBits {
+        match self {
+            TensorKind::Bool | TensorKind::UInt8 | TensorKind::Int8 => TypeBits::Bits8,
+            TensorKind::UInt16 | TensorKind::Int16 | TensorKind::Float16 | TensorKind::BFloat16 => {
+                TypeBits::Bits16
+            }
+            TensorKind::UInt32 | TensorKind::Int32 | TensorKind::Float32 => TypeBits::Bits32,
+            TensorKind::UInt64 | TensorKind::Int64 | TensorKind::Float64 => TypeBits::Bits64,
+            TensorKind::Quantized(scheme) => scheme.storage.type_bits(),
+        }
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.type_bits().bits()
+    }
+
+    pub fn signed(&self) -> bool {
+        matches!(self.type_kind(), TypeKind::SInt | TypeKind::Float)
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self.type_kind(), TypeKind::Float)
+    }
+
+    /// Builds the `TensorKind` for a `kind`/`bits` pair, panicking if the
+    /// combination isn't representable (see [`is_valid_numeric_type`]).
+    pub fn from_parts(kind: TypeKind, bits: TypeBits) -> Self {
+        assert!(
+            is_valid_numeric_type(kind, bits),
+            "{:?} is not a valid {}-bit type",
+            kind,
+            bits.bits()
+        );
+        match (kind, bits) {
+            (TypeKind::Bool, _) => TensorKind::Bool,
+            (TypeKind::UInt, TypeBits::Bits8) => TensorKind::UInt8,
+            (TypeKind::UInt, TypeBits::Bits16) => TensorKind::UInt16,
+            (TypeKind::UInt, TypeBits::Bits32) => TensorKind::UInt32,
+            (TypeKind::UInt, TypeBits::Bits64) => TensorKind::UInt64,
+            (TypeKind::SInt, TypeBits::Bits8) => TensorKind::Int8,
+            (TypeKind::SInt, TypeBits::Bits16) => TensorKind::Int16,
+            (TypeKind::SInt, TypeBits::Bits32) => TensorKind::Int32,
+            (TypeKind::SInt, TypeBits::Bits64) => TensorKind::Int64,
+            (TypeKind::Float, TypeBits::Bits16) => TensorKind::Float16,
+            (TypeKind::Float, TypeBits::Bits32) => TensorKind::Float32,
+            (TypeKind::Float, TypeBits::Bits64) => TensorKind::Float64,
+            (TypeKind::Float, TypeBits::Bits8) => unreachable!("rejected above"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScalarKind {
+    Bool,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Int8,
+    Int16,
     Int32,
     Int64,
+    Float16,
+    BFloat16,
     Float32,
     Float64,
-    Bool,
+}
+
+impl ScalarKind {
+    pub fn type_kind(&self) -> TypeKind {
+        match self {
+            ScalarKind::Bool => TypeKind::Bool,
+            ScalarKind::UInt8 | ScalarKind::UInt16 | ScalarKind::UInt32 | ScalarKind::UInt64 => {
+                TypeKind::UInt
+            }
+            ScalarKind::Int8 | ScalarKind::Int16 | ScalarKind::Int32 | ScalarKind::Int64 => {
+                TypeKind::SInt
+            }
+            ScalarKind::Float16
+            | ScalarKind::BFloat16
+            | ScalarKind::Float32
+            | ScalarKind::Float64 => TypeKind::Float,
+        }
+    }
+
+    pub fn type_bits(&self) -> TypeBits {
+        match self {
+            ScalarKind::Bool | ScalarKind::UInt8 | ScalarKind::Int8 => TypeBits::Bits8,
+            ScalarKind::UInt16 | ScalarKind::Int16 | ScalarKind::Float16 | ScalarKind::BFloat16 => {
+                TypeBits::Bits16
+            }
+            ScalarKind::UInt32 | ScalarKind::Int32 | ScalarKind::Float32 => TypeBits::Bits32,
+            ScalarKind::UInt64 | ScalarKind::Int64 | ScalarKind::Float64 => TypeBits::Bits64,
+        }
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.type_bits().bits()
+    }
+
+    pub fn signed(&self) -> bool {
+        matches!(self.type_kind(), TypeKind::SInt | TypeKind::Float)
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self.type_kind(), TypeKind::Float)
+    }
+
+    /// Builds the `ScalarKind` for a `kind`/`bits` pair, panicking if the
+    /// combination isn't representable (see [`is_valid_numeric_type`]).
+    pub fn from_parts(kind: TypeKind, bits: TypeBits) -> Self {
+        assert!(
+            is_valid_numeric_type(kind, bits),
+            "{:?} is not a valid {}-bit type",
+            kind,
+            bits.bits()
+        );
+        match (kind, bits) {
+            (TypeKind::Bool, _) => ScalarKind::Bool,
+            (TypeKind::UInt, TypeBits::Bits8) => ScalarKind::UInt8,
+            (TypeKind::UInt, TypeBits::Bits16) => ScalarKind::UInt16,
+            (TypeKind::UInt, TypeBits::Bits32) => ScalarKind::UInt32,
+            (TypeKind::UInt, TypeBits::Bits64) => ScalarKind::UInt64,
+            (TypeKind::SInt, TypeBits::Bits8) => ScalarKind::Int8,
+            (TypeKind::SInt, TypeBits::Bits16) => ScalarKind::Int16,
+            (TypeKind::SInt, TypeBits::Bits32) => ScalarKind::Int32,
+            (TypeKind::SInt, TypeBits::Bits64) => ScalarKind::Int64,
+            (TypeKind::Float, TypeBits::Bits16) => ScalarKind::Float16,
+            (TypeKind::Float, TypeBits::Bits32) => ScalarKind::Float32,
+            (TypeKind::Float, TypeBits::Bits64) => ScalarKind::Float64,
+            (TypeKind::Float, TypeBits::Bits8) => unreachable!("rejected above"),
+        }
+    }
+}
+
+/// How much information a conversion between two numeric types may discard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Convertibility {
+    /// There's no sensible bit-level conversion (e.g. bool <-> float).
+    Impossible,
+    /// The target type can't represent every value of the source type exactly.
+    Lossy,
+    /// Every value of the source type is exactly representable in the target type.
+    Lossless,
+}
+
+/// A numeric element type that can report its `TypeKind`/`TypeBits`, i.e.
+/// [`ScalarKind`] and [`TensorKind`].
+pub trait NumericKind {
+    fn type_kind(&self) -> TypeKind;
+    fn type_bits(&self) -> TypeBits;
+    /// Whether this is `bfloat16`, the only width shared by two float formats.
+    fn is_bfloat16(&self) -> bool;
+}
+
+impl NumericKind for ScalarKind {
+    fn type_kind(&self) -> TypeKind {
+        ScalarKind::type_kind(self)
+    }
+    fn type_bits(&self) -> TypeBits {
+        ScalarKind::type_bits(self)
+    }
+    fn is_bfloat16(&self) -> bool {
+        matches!(self, ScalarKind::BFloat16)
+    }
+}
+
+impl NumericKind for TensorKind {
+    fn type_kind(&self) -> TypeKind {
+        TensorKind::type_kind(self)
+    }
+    fn type_bits(&self) -> TypeBits {
+        TensorKind::type_bits(self)
+    }
+    fn is_bfloat16(&self) -> bool {
+        matches!(self, TensorKind::BFloat16)
+    }
+}
+
+/// Classifies converting `from` into `to`, following the same family/bit-width
+/// matrix as [`is_valid_numeric_type`]: widening is lossless (including
+/// unsigned -> signed and int -> float when the target has more bits),
+/// narrowing, signed -> unsigned and float -> int are lossy, and bool only ever
+/// converts losslessly to itself.
+pub fn convertibility<K: NumericKind>(from: &K, to: &K) -> Convertibility {
+    let (from_kind, from_bits) = (from.type_kind(), from.type_bits());
+    let (to_kind, to_bits) = (to.type_kind(), to.type_bits());
+
+    if from_kind == TypeKind::Bool || to_kind == TypeKind::Bool {
+        return if from_kind == to_kind {
+            Convertibility::Lossless
+        } else {
+            Convertibility::Impossible
+        };
+    }
+
+    // A signed source can hold negative values, which no unsigned target can
+    // represent regardless of width, so this direction is never lossless.
+    if from_kind == TypeKind::SInt && to_kind == TypeKind::UInt {
+        return Convertibility::Lossy;
+    }
+
+    // Truncating a float to an integer always discards the fractional part,
+    // no matter how much wider the integer target is.
+    if from_kind == TypeKind::Float && to_kind != TypeKind::Float {
+        return Convertibility::Lossy;
+    }
+
+    let widens = to_bits.bits() > from_bits.bits();
+    let same_type = to_bits.bits() == from_bits.bits()
+        && from_kind == to_kind
+        && from.is_bfloat16() == to.is_bfloat16();
+    if widens || same_type {
+        Convertibility::Lossless
+    } else {
+        Convertibility::Lossy
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -47,6 +371,53 @@ pub struct OtherType {
     pub ty: TokenStream,
 }
 
+/// An ONNX `Sequence`: a homogeneous, runtime-length list of tensors.
+#[derive(Debug, Clone)]
+pub struct SequenceType {
+    pub name: Ident,
+    pub elem: Box<Type>,
+}
+
+/// An ONNX `Optional`: a value that may or may not be present, used by
+/// control-flow and loop bodies.
+#[derive(Debug, Clone)]
+pub struct OptionalType {
+    pub name: Ident,
+    pub inner: Box<Type>,
+}
+
+impl SequenceType {
+    pub fn new<S: AsRef<str>>(name: S, elem: Type) -> Self {
+        if name.as_ref().is_empty() {
+            panic!("Sequence of Type {:?} was passed with empty name", elem);
+        }
+        Self {
+            name: Ident::new(name.as_ref(), Span::call_site()),
+            elem: Box::new(elem),
+        }
+    }
+    pub fn ty(&self) -> TokenStream {
+        let elem_ty = self.elem.ty();
+        quote! { Vec<#elem_ty> }
+    }
+}
+
+impl OptionalType {
+    pub fn new<S: AsRef<str>>(name: S, inner: Type) -> Self {
+        if name.as_ref().is_empty() {
+            panic!("Optional of Type {:?} was passed with empty name", inner);
+        }
+        Self {
+            name: Ident::new(name.as_ref(), Span::call_site()),
+            inner: Box::new(inner),
+        }
+    }
+    pub fn ty(&self) -> TokenStream {
+        let inner_ty = self.inner.ty();
+        quote! { Option<#inner_ty> }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Type {
     /// Tensor type.
@@ -58,6 +429,12 @@ pub enum Type {
     /// Shape type.
     Shape(ShapeType),
 
+    /// Sequence of a homogeneous element type.
+    Sequence(SequenceType),
+
+    /// A value that may or may not be present.
+    Optional(OptionalType),
+
     // Other type (more flexible type).
     Other(OtherType),
 }
@@ -68,6 +445,8 @@ impl Type {
             Type::Tensor(tensor) => &tensor.name,
             Type::Scalar(scalar) => &scalar.name,
             Type::Shape(shape) => &shape.name,
+            Type::Sequence(sequence) => &sequence.name,
+            Type::Optional(optional) => &optional.name,
             Type::Other(other) => &other.name,
         }
     }
@@ -76,6 +455,8 @@ impl Type {
             Type::Tensor(tensor) => tensor.ty(),
             Type::Scalar(scalar) => scalar.ty(),
             Type::Shape(shape) => shape.ty(),
+            Type::Sequence(sequence) => sequence.ty(),
+            Type::Optional(optional) => optional.ty(),
             Type::Other(other) => other.ty(),
         }
     }
@@ -93,11 +474,19 @@ impl ScalarType {
     }
     pub fn ty(&self) -> TokenStream {
         match self.kind {
+            ScalarKind::Bool => quote! { bool },
+            ScalarKind::UInt8 => quote! { u8 },
+            ScalarKind::UInt16 => quote! { u16 },
+            ScalarKind::UInt32 => quote! { u32 },
+            ScalarKind::UInt64 => quote! { u64 },
+            ScalarKind::Int8 => quote! { i8 },
+            ScalarKind::Int16 => quote! { i16 },
             ScalarKind::Int32 => quote! { i32 },
             ScalarKind::Int64 => quote! { i64 },
+            ScalarKind::Float16 => quote! { half::f16 },
+            ScalarKind::BFloat16 => quote! { half::bf16 },
             ScalarKind::Float32 => quote! { f32 },
             ScalarKind::Float64 => quote! { f64 },
-            ScalarKind::Bool => quote! { bool },
         }
     }
 }
@@ -134,7 +523,7 @@ impl TensorType {
         name: S,
         dim: usize,
         kind: TensorKind,
-        shape: Option<Vec<usize>>,
+        shape: Option<Vec<Dim>>,
     ) -> Self {
         if name.as_ref().is_empty() {
             panic!(
@@ -147,11 +536,17 @@ impl TensorType {
             dim, 0,
             "Trying to create TensorType with dim = 0 - should be a Scalar instead!"
         );
+        let concrete = shape.as_ref().and_then(|dims| {
+            dims.iter()
+                .map(Dim::as_concrete)
+                .collect::<Option<Vec<_>>>()
+        });
         Self {
             name: Ident::new(&formatted_name, Span::call_site()),
             dim,
             kind,
             shape,
+            concrete,
         }
     }
     pub fn new_float<S: AsRef<str>>(name: S, dim: usize) -> Self {
@@ -161,21 +556,17 @@ impl TensorType {
     pub fn new_float_with_shape<S: AsRef<str>>(
         name: S,
         dim: usize,
-        shape: Option<Vec<usize>>,
+        shape: Option<Vec<Dim>>,
     ) -> Self {
-        Self::new(name, dim, TensorKind::Float, shape)
+        Self::new(name, dim, TensorKind::Float32, shape)
     }
 
     pub fn new_int<S: AsRef<str>>(name: S, dim: usize) -> Self {
         Self::new_int_with_shape(name, dim, None)
     }
 
-    pub fn new_int_with_shape<S: AsRef<str>>(
-        name: S,
-        dim: usize,
-        shape: Option<Vec<usize>>,
-    ) -> Self {
-        Self::new(name, dim, TensorKind::Int, shape)
+    pub fn new_int_with_shape<S: AsRef<str>>(name: S, dim: usize, shape: Option<Vec<Dim>>) -> Self {
+        Self::new(name, dim, TensorKind::Int64, shape)
     }
 
     pub fn new_bool<S: AsRef<str>>(name: S, dim: usize) -> Self {
@@ -185,34 +576,99 @@ impl TensorType {
     pub fn new_bool_with_shape<S: AsRef<str>>(
         name: S,
         dim: usize,
-        shape: Option<Vec<usize>>,
+        shape: Option<Vec<Dim>>,
     ) -> Self {
         Self::new(name, dim, TensorKind::Bool, shape)
     }
 
+    pub fn new_quant<S: AsRef<str>>(name: S, dim: usize, scheme: QuantScheme) -> Self {
+        Self::new_quant_with_shape(name, dim, scheme, None)
+    }
+
+    pub fn new_quant_with_shape<S: AsRef<str>>(
+        name: S,
+        dim: usize,
+        scheme: QuantScheme,
+        shape: Option<Vec<Dim>>,
+    ) -> Self {
+        Self::new(name, dim, TensorKind::Quantized(scheme), shape)
+    }
+
+    /// Returns `true` when every axis of this tensor's shape is statically known.
+    ///
+    /// Tensors with no shape information at all (`shape == None`) are not concrete.
+    pub fn is_concrete(&self) -> bool {
+        self.concrete.is_some()
+    }
+
+    /// Returns the fully resolved shape, if every axis is `Dim::Concrete`.
+    pub fn as_concrete(&self) -> Option<&[usize]> {
+        self.concrete.as_deref()
+    }
+
+    /// The total element count of this tensor's shape, as a `TokenStream`.
+    ///
+    /// When the shape is fully concrete this is a single literal; otherwise the
+    /// concrete axes are folded into one literal factor and multiplied at runtime
+    /// by the symbolic axes, so the generated code queries those dims instead of
+    /// baking them in.
+    pub fn volume(&self) -> TokenStream {
+        let dims = self.shape.as_ref().unwrap_or_else(|| {
+            panic!("Tensor {} has no shape to compute a volume from", self.name)
+        });
+
+        let mut concrete_product: usize = 1;
+        let mut symbolic_factors = Vec::new();
+        for d in dims {
+            match d {
+                Dim::Concrete(size) => concrete_product *= size,
+                Dim::Symbolic(ident) => symbolic_factors.push(ident),
+            }
+        }
+
+        let concrete = concrete_product.to_tokens();
+        symbolic_factors.into_iter().fold(
+            quote! { #concrete },
+            |acc, ident| quote! { (#acc) * #ident },
+        )
+    }
+
+    /// The Burn tensor generic for this element type. Burn only distinguishes
+    /// tensors by family (float/int/bool), so types within a family (e.g. `u8`
+    /// and `i64`) share the same marker here; `self.kind` still carries the
+    /// precise width for callers that need it (e.g. cast legality).
     pub fn ty(&self) -> TokenStream {
         let dim = self.dim.to_tokens();
-        match self {
-            TensorType {
-                kind: TensorKind::Float,
-                ..
-            } => quote! {
+        if let TensorKind::Quantized(_) = &self.kind {
+            return quote! {
+                Tensor<B, #dim, QFloat>
+            };
+        }
+        match self.kind.type_kind() {
+            TypeKind::Float => quote! {
                 Tensor<B, #dim>
             },
-            TensorType {
-                kind: TensorKind::Int,
-                ..
-            } => quote! {
+            TypeKind::UInt | TypeKind::SInt => quote! {
                 Tensor<B, #dim, Int>
             },
-            TensorType {
-                kind: TensorKind::Bool,
-                ..
-            } => quote! {
+            TypeKind::Bool => quote! {
                 Tensor<B, #dim, Bool>
             },
         }
     }
+
+    /// The call converting this tensor to `target`'s element kind, along with
+    /// how lossy that conversion is so the importer can warn on `Lossy` and
+    /// reject `Impossible` casts at compile time.
+    pub fn cast_to(&self, target: TensorKind) -> (TokenStream, Convertibility) {
+        let name = &self.name;
+        let call = match target.type_kind() {
+            TypeKind::Float => quote! { #name.float() },
+            TypeKind::UInt | TypeKind::SInt => quote! { #name.int() },
+            TypeKind::Bool => quote! { #name.bool() },
+        };
+        (call, convertibility(&self.kind, &target))
+    }
 }
 
 impl OtherType {
@@ -232,3 +688,264 @@ impl OtherType {
         self.ty.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbolic(name: &str) -> Dim {
+        Dim::Symbolic(Ident::new(name, Span::call_site()))
+    }
+
+    #[test]
+    fn concrete_shape_is_concrete() {
+        let tensor = TensorType::new_float_with_shape(
+            "x",
+            2,
+            Some(vec![Dim::Concrete(1), Dim::Concrete(128)]),
+        );
+
+        assert!(tensor.is_concrete());
+        assert_eq!(tensor.as_concrete(), Some([1, 128].as_slice()));
+    }
+
+    #[test]
+    fn symbolic_shape_is_not_concrete() {
+        let tensor = TensorType::new_float_with_shape(
+            "x",
+            2,
+            Some(vec![symbolic("batch"), Dim::Concrete(128)]),
+        );
+
+        assert!(!tensor.is_concrete());
+        assert_eq!(tensor.as_concrete(), None);
+    }
+
+    #[test]
+    fn missing_shape_is_not_concrete() {
+        let tensor = TensorType::new_float("x", 2);
+
+        assert!(!tensor.is_concrete());
+        assert_eq!(tensor.as_concrete(), None);
+    }
+
+    #[test]
+    fn volume_of_concrete_shape() {
+        let tensor = TensorType::new_float_with_shape(
+            "x",
+            2,
+            Some(vec![Dim::Concrete(4), Dim::Concrete(8)]),
+        );
+
+        assert_eq!(tensor.volume().to_string(), 32usize.to_tokens().to_string());
+    }
+
+    #[test]
+    fn volume_of_mixed_shape_multiplies_symbolic_dims_at_runtime() {
+        let tensor = TensorType::new_float_with_shape(
+            "x",
+            3,
+            Some(vec![
+                symbolic("batch"),
+                Dim::Concrete(128),
+                Dim::Concrete(768),
+            ]),
+        );
+
+        let volume = tensor.volume().to_string();
+        assert!(volume.contains(&98304usize.to_tokens().to_string()));
+        assert!(volume.contains("batch"));
+    }
+
+    const ALL_BITS: [TypeBits; 4] = [
+        TypeBits::Bits8,
+        TypeBits::Bits16,
+        TypeBits::Bits32,
+        TypeBits::Bits64,
+    ];
+    const ALL_KINDS: [TypeKind; 4] = [
+        TypeKind::Bool,
+        TypeKind::UInt,
+        TypeKind::SInt,
+        TypeKind::Float,
+    ];
+
+    #[test]
+    fn valid_scalar_kind_combinations_round_trip() {
+        for &kind in &ALL_KINDS {
+            for &bits in &ALL_BITS {
+                if !is_valid_numeric_type(kind, bits) {
+                    continue;
+                }
+                let scalar = ScalarKind::from_parts(kind, bits);
+                assert_eq!(scalar.type_kind(), kind);
+                assert_eq!(scalar.bits(), bits.bits());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "not a valid")]
+    fn float_at_8_bits_is_rejected() {
+        ScalarKind::from_parts(TypeKind::Float, TypeBits::Bits8);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a valid")]
+    fn bool_wider_than_8_bits_is_rejected() {
+        ScalarKind::from_parts(TypeKind::Bool, TypeBits::Bits16);
+    }
+
+    #[test]
+    fn scalar_kind_signed_and_float_predicates() {
+        assert!(!ScalarKind::Bool.signed());
+        assert!(!ScalarKind::UInt8.signed());
+        assert!(ScalarKind::Int32.signed());
+        assert!(ScalarKind::Float32.signed());
+
+        assert!(ScalarKind::Float16.is_float());
+        assert!(ScalarKind::BFloat16.is_float());
+        assert!(!ScalarKind::Int64.is_float());
+    }
+
+    #[test]
+    fn valid_tensor_kind_combinations_round_trip() {
+        for &kind in &ALL_KINDS {
+            for &bits in &ALL_BITS {
+                if !is_valid_numeric_type(kind, bits) {
+                    continue;
+                }
+                let tensor_kind = TensorKind::from_parts(kind, bits);
+                assert_eq!(tensor_kind.type_kind(), kind);
+                assert_eq!(tensor_kind.bits(), bits.bits());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "not a valid")]
+    fn tensor_kind_bool_wider_than_8_bits_is_rejected() {
+        TensorKind::from_parts(TypeKind::Bool, TypeBits::Bits16);
+    }
+
+    #[test]
+    fn tensor_kind_signed_and_float_predicates() {
+        assert!(!TensorKind::Bool.signed());
+        assert!(!TensorKind::UInt8.signed());
+        assert!(TensorKind::Int32.signed());
+        assert!(TensorKind::Float32.signed());
+
+        assert!(TensorKind::Float16.is_float());
+        assert!(TensorKind::BFloat16.is_float());
+        assert!(!TensorKind::Int64.is_float());
+    }
+
+    #[test]
+    fn tensor_ty_groups_by_family_regardless_of_width() {
+        let uint8 = TensorType::new("t", 2, TensorKind::UInt8, None)
+            .ty()
+            .to_string();
+        let int64 = TensorType::new("t", 2, TensorKind::Int64, None)
+            .ty()
+            .to_string();
+        assert_eq!(uint8, int64);
+
+        let float16 = TensorType::new("t", 2, TensorKind::Float16, None)
+            .ty()
+            .to_string();
+        let float64 = TensorType::new("t", 2, TensorKind::Float64, None)
+            .ty()
+            .to_string();
+        assert_eq!(float16, float64);
+    }
+
+    #[test]
+    fn convertibility_truth_table() {
+        use Convertibility::{Impossible, Lossless, Lossy};
+        use ScalarKind::*;
+
+        let cases = [
+            // same family, widening is lossless
+            (Int32, Int64, Lossless),
+            (UInt8, UInt32, Lossless),
+            (Float32, Float64, Lossless),
+            (Float16, Float32, Lossless),
+            // same family, narrowing or no-op-width is lossy/lossless
+            (Int64, Int32, Lossy),
+            (Float64, Float32, Lossy),
+            (UInt8, UInt8, Lossless),
+            // crossing signedness
+            (UInt8, Int16, Lossless),
+            (UInt32, Int32, Lossy),
+            // signed -> unsigned can never be lossless: negative values have
+            // no unsigned representation, no matter how much the target widens
+            (Int8, UInt16, Lossy),
+            (Int8, UInt64, Lossy),
+            // crossing the int/float divide
+            (Int32, Float64, Lossless),
+            (Int32, Float32, Lossy),
+            (Int64, Float64, Lossy),
+            (Float32, Int32, Lossy),
+            (Float64, Int64, Lossy),
+            // float -> int truncates the fractional part even when the
+            // integer target is wider than the float source
+            (Float32, Int64, Lossy),
+            (Float16, UInt32, Lossy),
+            // bool only converts losslessly to itself
+            (Bool, Bool, Lossless),
+            (Bool, Int32, Impossible),
+            (Float32, Bool, Impossible),
+        ];
+
+        for (from, to, expected) in cases {
+            assert_eq!(
+                convertibility(&from, &to),
+                expected,
+                "{:?} -> {:?}",
+                from,
+                to
+            );
+        }
+    }
+
+    #[test]
+    fn sequence_of_float_tensors_generates_vec_of_tensor() {
+        let elem_ty = TensorType::new_float("x", 3).ty();
+        let sequence = SequenceType::new("xs", Type::Tensor(TensorType::new_float("x", 3)));
+
+        assert_eq!(
+            sequence.ty().to_string(),
+            quote! { Vec<#elem_ty> }.to_string()
+        );
+    }
+
+    #[test]
+    fn optional_scalar_generates_option_of_scalar() {
+        let optional =
+            OptionalType::new("x", Type::Scalar(ScalarType::new("x", ScalarKind::Float32)));
+
+        assert_eq!(
+            optional.ty().to_string(),
+            quote! { Option<f32> }.to_string()
+        );
+    }
+
+    #[test]
+    fn per_tensor_quantized_input_round_trips_scale_and_zero_point() {
+        let scheme = QuantScheme {
+            scale: QuantScale::Concrete(0.0078125),
+            zero_point: 128,
+            storage: ScalarKind::UInt8,
+        };
+        let tensor = TensorType::new_quant("x", 2, scheme.clone());
+
+        assert_eq!(
+            tensor.ty().to_string(),
+            quote! { Tensor<B, 2, QFloat> }.to_string()
+        );
+        match &tensor.kind {
+            TensorKind::Quantized(round_tripped) => assert_eq!(round_tripped, &scheme),
+            other => panic!("expected a quantized tensor kind, got {:?}", other),
+        }
+    }
+}